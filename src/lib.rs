@@ -6,7 +6,74 @@
 #![warn(missing_docs)]
 #![warn(unsafe_code)]
 
-use time::{Date, Month, Time};
+use std::error::Error;
+use std::fmt;
+
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+/// Errors that can occur when parsing a FAT DOS date or time field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FatDateTimeError {
+    /// A field's raw value is outside the valid range for that FAT field,
+    /// e.g. an hour of 24, a month of 0 or 13, or a day of 0.
+    OutOfRange,
+    /// Every field is individually in range, but together they do not form
+    /// a real calendar date, e.g. February 31st.
+    Impossible,
+    /// The value is the all-zero reserved field FAT uses to mean "unset".
+    Reserved,
+}
+
+impl fmt::Display for FatDateTimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FatDateTimeError::OutOfRange => {
+                write!(f, "field value is out of FAT's representable range")
+            }
+            FatDateTimeError::Impossible => {
+                write!(f, "fields do not form a valid calendar date")
+            }
+            FatDateTimeError::Reserved => write!(f, "value is the reserved all-zero field"),
+        }
+    }
+}
+
+impl Error for FatDateTimeError {}
+
+/// Parse a FAT DOS time, returning an error describing why on failure.
+///
+/// From FAT: General Overview of On-Disk Format \
+/// MS-DOS epoch is 01/01/1980 \
+/// Bits 0-4: 2-second count, valid value range 0-29 inclusive (0 - 58 seconds). \
+/// Bits 5-10: Minutes, valid value range 0-59 inclusive. \
+/// Bits 11-15: Hours, valid value range 0-23 inclusive. \
+///
+/// # Examples
+///
+/// ```
+/// use fat_date_time::try_parse_fat_time;
+///
+/// let time = try_parse_fat_time(0xbf7d);
+///
+/// assert!(time.is_ok());
+/// assert_eq!(time.unwrap().hour(), 23);
+/// ```
+pub fn try_parse_fat_time(dos_time: u16) -> Result<Time, FatDateTimeError> {
+    let hours = ((dos_time >> 11) as u8) & 0x1F;
+    if hours > 23 {
+        return Err(FatDateTimeError::OutOfRange);
+    }
+    let minutes = ((dos_time >> 5) as u8) & 0x3F;
+    if minutes > 59 {
+        return Err(FatDateTimeError::OutOfRange);
+    }
+    let seconds = (dos_time & 0x1F) as u8;
+    if seconds > 29 {
+        return Err(FatDateTimeError::OutOfRange);
+    }
+
+    Time::from_hms(hours, minutes, seconds * 2).map_err(|_| FatDateTimeError::Impossible)
+}
 
 /// Parse a FAT DOS time.
 /// Assume a value of zero is an invalid date / reserved field
@@ -34,29 +101,10 @@ pub fn parse_fat_time(dos_time: u16) -> Option<Time> {
     // Assume a value of zero is an "invalid" time and the field is a
     // "reserved" field
     // This isn't always true, some utilities may not write a time
-    let hours = ((dos_time >> 11) as u8) & 0x1F;
-    if hours > 23 {
-        return None;
-    }
-    let minutes = ((dos_time >> 5) as u8) & 0x3F;
-    if minutes > 59 {
-        return None;
-    }
-    let seconds = (dos_time & 0x1F) as u8;
-    if seconds > 29 {
-        return None;
-    }
-
-    let time = Time::from_hms(hours, minutes, seconds * 2);
-
-    match time {
-        Ok(t) => Some(t),
-        Err(e) => panic!("Couldn't parse time: {}", e),
-    }
+    try_parse_fat_time(dos_time).ok()
 }
 
-/// Parse a FAT DOS date.
-/// If a date is invalid, a value of None is returned.
+/// Parse a FAT DOS date, returning an error describing why on failure.
 ///
 /// From FAT: General Overview of On-Disk Format \
 /// The valid time range is from Midnight 00:00:00 to 23:59:58. \
@@ -67,29 +115,25 @@ pub fn parse_fat_time(dos_time: u16) -> Option<Time> {
 /// # Examples
 ///
 /// ```
-/// use fat_date_time::parse_fat_date;
-/// use time::Month;
+/// use fat_date_time::try_parse_fat_date;
 ///
-/// let date = parse_fat_date(0xff9f);
+/// let date = try_parse_fat_date(0xff9f);
 ///
-/// assert!(date.is_some());
-/// assert_eq!(date.unwrap().year(), 2107);
-/// assert_eq!(date.unwrap().month(), Month::December);
+/// assert!(date.is_ok());
 /// assert_eq!(date.unwrap().day(), 31);
-///
 /// ```
-pub fn parse_fat_date(dos_date: u16) -> Option<Date> {
+pub fn try_parse_fat_date(dos_date: u16) -> Result<Date, FatDateTimeError> {
     // Assume a value of zero is an "invalid" date and the field is a
     // "reserved" field
     // This isn't always true, some utilities may not write a date
     if dos_date == 0 {
-        return None;
+        return Err(FatDateTimeError::Reserved);
     }
 
     let year: i32 = ((dos_date >> 9) & 0x7F) as i32;
     // equivalent to (year < 0) || (year > 127)
     if !(0..=127).contains(&year) {
-        return None;
+        return Err(FatDateTimeError::OutOfRange);
     }
 
     let year = year + 1980;
@@ -109,7 +153,7 @@ pub fn parse_fat_date(dos_date: u16) -> Option<Date> {
         10 => Month::October,
         11 => Month::November,
         12 => Month::December,
-        _ => return None,
+        _ => return Err(FatDateTimeError::OutOfRange),
     };
 
     let day = (dos_date & 0x1F) as u8;
@@ -117,15 +161,235 @@ pub fn parse_fat_date(dos_date: u16) -> Option<Date> {
     // Check that the day value is in range
     // equivalent to (day < 1) || (day > 31)
     if !(1..=31).contains(&day) {
+        return Err(FatDateTimeError::OutOfRange);
+    }
+
+    Date::from_calendar_date(year, month, day).map_err(|_| FatDateTimeError::Impossible)
+}
+
+/// Parse a FAT DOS date.
+/// If a date is invalid, a value of None is returned.
+///
+/// From FAT: General Overview of On-Disk Format \
+/// The valid time range is from Midnight 00:00:00 to 23:59:58. \
+/// Bits 0-4: Day of month, valid value range 1-31 inclusive. \
+/// Bits 5-8: Month of year, 1 = January, valid value range 1-12 inclusive. \
+/// Bits 9-15: Count of years from 1980, valid value range 0-127 inclusive (1980-2107). \
+///
+/// # Examples
+///
+/// ```
+/// use fat_date_time::parse_fat_date;
+/// use time::Month;
+///
+/// let date = parse_fat_date(0xff9f);
+///
+/// assert!(date.is_some());
+/// assert_eq!(date.unwrap().year(), 2107);
+/// assert_eq!(date.unwrap().month(), Month::December);
+/// assert_eq!(date.unwrap().day(), 31);
+///
+/// ```
+pub fn parse_fat_date(dos_date: u16) -> Option<Date> {
+    try_parse_fat_date(dos_date).ok()
+}
+
+/// Encode a [`Time`] as a FAT DOS time.
+///
+/// Returns `None` if the time cannot be represented in the FAT bit
+/// layout, and rounds the seconds down to the nearest even value since
+/// FAT only stores a 2-second count.
+///
+/// From FAT: General Overview of On-Disk Format \
+/// Bits 0-4: 2-second count, valid value range 0-29 inclusive (0 - 58 seconds). \
+/// Bits 5-10: Minutes, valid value range 0-59 inclusive. \
+/// Bits 11-15: Hours, valid value range 0-23 inclusive. \
+///
+/// # Examples
+///
+/// ```
+/// use fat_date_time::encode_fat_time;
+/// use time::Time;
+///
+/// let time = Time::from_hms(23, 59, 59).unwrap();
+///
+/// assert_eq!(encode_fat_time(&time), Some(0xbf7d));
+/// ```
+pub fn encode_fat_time(time: &Time) -> Option<u16> {
+    let hours = time.hour();
+    if hours > 23 {
+        return None;
+    }
+
+    let minutes = time.minute();
+    if minutes > 59 {
+        return None;
+    }
+
+    let seconds = (time.second() / 2) as u16;
+
+    Some(((hours as u16) << 11) | ((minutes as u16) << 5) | seconds)
+}
+
+/// Encode a [`Date`] as a FAT DOS date.
+///
+/// Returns `None` if the date is outside the range FAT can represent
+/// (years before 1980 or after 2107).
+///
+/// From FAT: General Overview of On-Disk Format \
+/// Bits 0-4: Day of month, valid value range 1-31 inclusive. \
+/// Bits 5-8: Month of year, 1 = January, valid value range 1-12 inclusive. \
+/// Bits 9-15: Count of years from 1980, valid value range 0-127 inclusive (1980-2107). \
+///
+/// # Examples
+///
+/// ```
+/// use fat_date_time::encode_fat_date;
+/// use time::{Date, Month};
+///
+/// let date = Date::from_calendar_date(2107, Month::December, 31).unwrap();
+///
+/// assert_eq!(encode_fat_date(&date), Some(0xff9f));
+/// ```
+pub fn encode_fat_date(date: &Date) -> Option<u16> {
+    let year = date.year() - 1980;
+    if !(0..=127).contains(&year) {
         return None;
     }
 
-    let date = Date::from_calendar_date(year, month, day);
+    let month = date.month() as u16;
+    let day = date.day() as u16;
+
+    Some(((year as u16) << 9) | (month << 5) | day)
+}
 
-    match date {
-        Ok(d) => Some(d),
-        Err(e) => panic!("Couldn't parse date: {}", e),
+/// Parse a combined 32-bit FAT date+time field into a [`PrimitiveDateTime`].
+///
+/// FAT directory entries store the date word in the high 16 bits and the
+/// time word in the low 16 bits of a 32-bit value. This splits the two
+/// halves and reuses [`parse_fat_date`] and [`parse_fat_time`], so the
+/// validation rules are identical to parsing the halves separately.
+///
+/// # Examples
+///
+/// ```
+/// use fat_date_time::parse_fat_datetime;
+///
+/// let datetime = parse_fat_datetime(0xff9fbf7d);
+///
+/// assert!(datetime.is_some());
+/// assert_eq!(datetime.unwrap().year(), 2107);
+/// assert_eq!(datetime.unwrap().hour(), 23);
+/// ```
+pub fn parse_fat_datetime(dos_datetime: u32) -> Option<PrimitiveDateTime> {
+    let date = parse_fat_date((dos_datetime >> 16) as u16)?;
+    let time = parse_fat_time(dos_datetime as u16)?;
+
+    Some(PrimitiveDateTime::new(date, time))
+}
+
+/// Encode a [`PrimitiveDateTime`] as a combined 32-bit FAT date+time field.
+///
+/// The date is placed in the high 16 bits and the time in the low 16
+/// bits, mirroring [`parse_fat_datetime`].
+///
+/// # Examples
+///
+/// ```
+/// use fat_date_time::encode_fat_datetime;
+/// use time::{Date, Month, PrimitiveDateTime, Time};
+///
+/// let date = Date::from_calendar_date(2107, Month::December, 31).unwrap();
+/// let time = Time::from_hms(23, 59, 59).unwrap();
+/// let datetime = PrimitiveDateTime::new(date, time);
+///
+/// assert_eq!(encode_fat_datetime(&datetime), Some(0xff9fbf7d));
+/// ```
+pub fn encode_fat_datetime(datetime: &PrimitiveDateTime) -> Option<u32> {
+    let date = encode_fat_date(&datetime.date())?;
+    let time = encode_fat_time(&datetime.time())?;
+
+    Some(((date as u32) << 16) | (time as u32))
+}
+
+/// Parse an exFAT timestamp, combining a 32-bit FAT date+time field with
+/// its one-byte UTC-offset field into an [`OffsetDateTime`].
+///
+/// exFAT extends the classic FAT timestamp with a UTC offset encoded in
+/// 15-minute increments: bit 7 is a "valid" flag, and the low 7 bits are
+/// a two's-complement count of quarter-hours. If the valid flag is
+/// clear, the offset is unknown, so the timestamp is assumed to be UTC.
+///
+/// # Examples
+///
+/// ```
+/// use fat_date_time::parse_exfat_datetime;
+///
+/// // Valid flag set, offset of +4 quarter-hours (one hour)
+/// let datetime = parse_exfat_datetime(0xff9fbf7d, 0x84);
+///
+/// assert!(datetime.is_some());
+/// assert_eq!(datetime.unwrap().offset().whole_hours(), 1);
+/// ```
+pub fn parse_exfat_datetime(dos_datetime: u32, utc_offset: u8) -> Option<OffsetDateTime> {
+    let datetime = parse_fat_datetime(dos_datetime)?;
+
+    // Bit 7 clear means the offset is unknown; assume UTC rather than
+    // guessing at a local time.
+    if utc_offset & 0x80 == 0 {
+        return Some(datetime.assume_utc());
+    }
+
+    let raw = (utc_offset & 0x7F) as i16;
+    let quarter_hours = if raw >= 64 { raw - 128 } else { raw };
+
+    // The two's-complement 7-bit range is already within roughly ±16
+    // hours, but check explicitly rather than relying on that.
+    if !(-64..=63).contains(&quarter_hours) {
+        return None;
     }
+
+    let offset = UtcOffset::from_whole_seconds(quarter_hours as i32 * 15 * 60).ok()?;
+
+    Some(datetime.assume_offset(offset))
+}
+
+/// Parse a FAT DOS time together with its fine-resolution "tenths" byte,
+/// recovering the millisecond precision used for file creation times.
+///
+/// The tenths byte holds 0-199 units of 10 ms; values of 100 or more also
+/// add one whole second to the time word, since FAT's 2-second count
+/// cannot represent odd seconds on its own. Returns `None` if `tenths`
+/// is greater than 199 or the underlying time word is invalid.
+///
+/// # Examples
+///
+/// ```
+/// use fat_date_time::parse_fat_time_with_tenths;
+///
+/// let time = parse_fat_time_with_tenths(0xbf7d, 150);
+///
+/// assert!(time.is_some());
+/// assert_eq!(time.unwrap().second(), 59);
+/// assert_eq!(time.unwrap().millisecond(), 500);
+/// ```
+pub fn parse_fat_time_with_tenths(dos_time: u16, tenths: u8) -> Option<Time> {
+    if tenths > 199 {
+        return None;
+    }
+
+    let time = parse_fat_time(dos_time)?;
+
+    let extra_seconds = tenths / 100;
+    let milliseconds = (tenths % 100) as u16 * 10;
+
+    Time::from_hms_milli(
+        time.hour(),
+        time.minute(),
+        time.second() + extra_seconds,
+        milliseconds,
+    )
+    .ok()
 }
 
 #[cfg(test)]
@@ -205,6 +469,162 @@ mod tests {
         assert!(time.is_none());
     }
 
+    #[test]
+    fn try_parse_fat_date_reports_error_kinds() {
+        // Value of zero is the reserved field
+        assert_eq!(try_parse_fat_date(0), Err(FatDateTimeError::Reserved));
+
+        // Month value out of its bit-range
+        let date = try_parse_fat_date(0b0000000110100001);
+        assert_eq!(date, Err(FatDateTimeError::OutOfRange));
+
+        // Day value out of its bit-range
+        let date = try_parse_fat_date(0b0000000000100000);
+        assert_eq!(date, Err(FatDateTimeError::OutOfRange));
+
+        // Individually in-range fields that form no real calendar date:
+        // February 31st, 1980
+        let date = try_parse_fat_date(0b0000000001011111);
+        assert_eq!(date, Err(FatDateTimeError::Impossible));
+    }
+
+    #[test]
+    fn try_parse_fat_time_reports_error_kinds() {
+        // Second value out of its bit-range
+        let time = try_parse_fat_time(0b1011111101111110);
+        assert_eq!(time, Err(FatDateTimeError::OutOfRange));
+
+        // Hour value out of its bit-range
+        let time = try_parse_fat_time(0b1100011101111101);
+        assert_eq!(time, Err(FatDateTimeError::OutOfRange));
+    }
+
+    #[test]
+    fn encode_fat_date_works() {
+        // The earliest possible "valid" date
+        let date = Date::from_calendar_date(1980, Month::January, 1).unwrap();
+        assert_eq!(encode_fat_date(&date), Some(0b0000000000100001));
+
+        // The latest possible date
+        let date = Date::from_calendar_date(2107, Month::December, 31).unwrap();
+        assert_eq!(encode_fat_date(&date), Some(0b1111111110011111));
+
+        // Out of FAT's representable range
+        let date = Date::from_calendar_date(1979, Month::December, 31).unwrap();
+        assert_eq!(encode_fat_date(&date), None);
+
+        let date = Date::from_calendar_date(2108, Month::January, 1).unwrap();
+        assert_eq!(encode_fat_date(&date), None);
+    }
+
+    #[test]
+    fn encode_fat_time_works() {
+        // Test the earliest possible time
+        let time = Time::from_hms(0, 0, 0).unwrap();
+        assert_eq!(encode_fat_time(&time), Some(0));
+
+        // Test the latest possible time, with an odd second rounded down
+        let time = Time::from_hms(23, 59, 59).unwrap();
+        assert_eq!(encode_fat_time(&time), Some(0b1011111101111101));
+    }
+
+    #[test]
+    fn encode_decode_fat_date_round_trips() {
+        let date = parse_fat_date(0xFF9F).unwrap();
+        assert_eq!(encode_fat_date(&date), Some(0xFF9F));
+    }
+
+    #[test]
+    fn encode_decode_fat_time_round_trips() {
+        let time = parse_fat_time(0x477D).unwrap();
+        assert_eq!(encode_fat_time(&time), Some(0x477D));
+    }
+
+    #[test]
+    fn parse_fat_datetime_works() {
+        let datetime = parse_fat_datetime(0xff9fbf7d);
+
+        assert!(datetime.is_some());
+        let datetime = datetime.unwrap();
+        assert_eq!(datetime.year(), 2107);
+        assert_eq!(datetime.month(), Month::December);
+        assert_eq!(datetime.day(), 31);
+        assert_eq!(datetime.hour(), 23);
+        assert_eq!(datetime.minute(), 59);
+        assert_eq!(datetime.second(), 58);
+
+        // An invalid date word invalidates the whole field
+        let datetime = parse_fat_datetime(0x0000bf7d);
+        assert!(datetime.is_none());
+
+        // An invalid time word invalidates the whole field
+        let datetime = parse_fat_datetime(0xff9f0000 | 0b1100011101111101);
+        assert!(datetime.is_none());
+    }
+
+    #[test]
+    fn encode_fat_datetime_works() {
+        let date = Date::from_calendar_date(2107, Month::December, 31).unwrap();
+        let time = Time::from_hms(23, 59, 59).unwrap();
+        let datetime = PrimitiveDateTime::new(date, time);
+
+        assert_eq!(encode_fat_datetime(&datetime), Some(0xff9fbf7d));
+    }
+
+    #[test]
+    fn encode_decode_fat_datetime_round_trips() {
+        let datetime = parse_fat_datetime(0xff9fbf7d).unwrap();
+        assert_eq!(encode_fat_datetime(&datetime), Some(0xff9fbf7d));
+    }
+
+    #[test]
+    fn parse_exfat_datetime_works() {
+        // Valid flag clear: offset is unknown, assume UTC
+        let datetime = parse_exfat_datetime(0xff9fbf7d, 0x00);
+        assert!(datetime.is_some());
+        assert_eq!(datetime.unwrap().offset(), UtcOffset::UTC);
+
+        // Valid flag set, positive offset of +4 quarter-hours (one hour)
+        let datetime = parse_exfat_datetime(0xff9fbf7d, 0x84);
+        assert!(datetime.is_some());
+        assert_eq!(datetime.unwrap().offset().whole_hours(), 1);
+
+        // Valid flag set, negative offset (two's complement) of -64
+        // quarter-hours (16 hours)
+        let datetime = parse_exfat_datetime(0xff9fbf7d, 0b11000000);
+        assert!(datetime.is_some());
+        assert_eq!(datetime.unwrap().offset().whole_hours(), -16);
+
+        // An invalid underlying date+time field still fails to parse
+        let datetime = parse_exfat_datetime(0x0000bf7d, 0x84);
+        assert!(datetime.is_none());
+    }
+
+    #[test]
+    fn parse_fat_time_with_tenths_works() {
+        // Tenths below 100 only add milliseconds
+        let time = parse_fat_time_with_tenths(0, 55);
+        assert!(time.is_some());
+        let time = time.unwrap();
+        assert_eq!(time.second(), 0);
+        assert_eq!(time.millisecond(), 550);
+
+        // Tenths at or above 100 also add a whole second
+        let time = parse_fat_time_with_tenths(0xbf7d, 150);
+        assert!(time.is_some());
+        let time = time.unwrap();
+        assert_eq!(time.second(), 59);
+        assert_eq!(time.millisecond(), 500);
+
+        // Out of range tenths value
+        let time = parse_fat_time_with_tenths(0, 200);
+        assert!(time.is_none());
+
+        // An invalid underlying time word still fails to parse
+        let time = parse_fat_time_with_tenths(0b1100011101111101, 0);
+        assert!(time.is_none());
+    }
+
     /// Tests from pyfatfs Python module
     #[test]
     fn external_tests_pass() {